@@ -2,6 +2,8 @@ use prettytable::format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR;
 use prettytable::{Cell, Row, Table};
 use std::cell::RefCell;
 use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
 #[derive(Default, Clone, Copy, PartialEq, Eq)]
 pub struct InstrumentedBase {
@@ -14,11 +16,28 @@ impl InstrumentedBase {
     const EQ: usize = 3;
     const PARTIAL_CMP: usize = 4;
     const CMP: usize = 5;
+    const ASSIGN: usize = 6;
+    const SWAP: usize = 7;
+    const DEFAULT: usize = 8;
+    const KEY_EVAL: usize = 9;
+    const HASH: usize = 10;
 
-    const COLUMNS: usize = 6;
+    const COLUMNS: usize = 11;
 
     pub fn counts_names() -> [&'static str; InstrumentedBase::COLUMNS] {
-        ["new", "clone", "drop", "eq", "partial_cmp", "cmp"]
+        [
+            "new",
+            "clone",
+            "drop",
+            "eq",
+            "partial_cmp",
+            "cmp",
+            "assign",
+            "swap",
+            "default",
+            "key_eval",
+            "hash",
+        ]
     }
 
     pub fn set(&mut self, c: [usize; InstrumentedBase::COLUMNS]) {
@@ -58,6 +77,27 @@ impl<T> Instrumented<T> {
         base.borrow_mut().counts[InstrumentedBase::NEW] += 1;
         Self { value, base }
     }
+
+    /// Reads the wrapped value without counting an operation, so callers
+    /// can derive keys, print, or otherwise inspect `T` from outside the
+    /// crate.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+}
+
+/// Default constructible
+impl<T> Instrumented<T>
+where
+    T: Default,
+{
+    pub fn default(base: Rc<RefCell<InstrumentedBase>>) -> Self {
+        base.borrow_mut().counts[InstrumentedBase::DEFAULT] += 1;
+        Self {
+            value: T::default(),
+            base,
+        }
+    }
 }
 
 /// Semi regular
@@ -74,6 +114,17 @@ where
     }
 }
 
+/// Semi regular
+impl<T> Instrumented<T>
+where
+    T: Clone,
+{
+    pub fn assign(&mut self, x: &Self) {
+        self.base.borrow_mut().counts[InstrumentedBase::ASSIGN] += 1;
+        self.value.clone_from(&x.value);
+    }
+}
+
 /// Semi regular
 impl<T> Drop for Instrumented<T> {
     fn drop(&mut self) {
@@ -92,6 +143,17 @@ where
     }
 }
 
+/// Regular
+impl<T> std::hash::Hash for Instrumented<T>
+where
+    T: std::hash::Hash,
+{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.base.borrow_mut().counts[InstrumentedBase::HASH] += 1;
+        self.value.hash(state);
+    }
+}
+
 /// Totally-ordered
 impl<T> PartialOrd for Instrumented<T>
 where
@@ -114,32 +176,297 @@ where
     }
 }
 
-pub fn table_count_operations<F>(mut i: usize, j: usize, f: F)
+/// Semi regular
+pub fn swap<T>(a: &mut Instrumented<T>, b: &mut Instrumented<T>) {
+    a.base.borrow_mut().counts[InstrumentedBase::SWAP] += 1;
+    std::mem::swap(&mut a.value, &mut b.value);
+}
+
+pub fn table_count_operations<F>(i: usize, j: usize, f: F)
 where
     F: Fn(&mut [Instrumented<u64>]),
 {
+    table_count_operations_for(i, j, InputDistribution::RandomPermutation, f);
+}
+
+/// Input distributions that [`table_count_operations_for`] can generate,
+/// letting callers probe best/worst/adversarial behavior instead of only a
+/// single random shuffle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputDistribution {
+    Sorted,
+    Reversed,
+    NearlySorted { swaps: usize },
+    FewUniqueValues { classes: usize },
+    Sawtooth { period: usize },
+    RandomPermutation,
+}
+
+fn generate_vec(i: usize, dist: InputDistribution) -> Vec<u64> {
+    match dist {
+        InputDistribution::Sorted => (0..i as u64).collect(),
+        InputDistribution::Reversed => (0..i as u64).rev().collect(),
+        InputDistribution::NearlySorted { swaps } => {
+            use rand::Rng;
+            let mut vec: Vec<u64> = (0..i as u64).collect();
+            if i >= 2 {
+                let mut rnd = rand::thread_rng();
+                for _ in 0..swaps {
+                    let a = rnd.gen_range(0..i);
+                    let b = rnd.gen_range(0..i);
+                    vec.swap(a, b);
+                }
+            }
+            vec
+        }
+        InputDistribution::FewUniqueValues { classes } => {
+            use rand::seq::SliceRandom;
+            let classes = classes.max(1);
+            let mut vec: Vec<u64> = (0..i).map(|k| (k % classes) as u64).collect();
+            vec.shuffle(&mut rand::thread_rng());
+            vec
+        }
+        InputDistribution::Sawtooth { period } => {
+            let period = period.max(1);
+            (0..i).map(|k| (k % period) as u64).collect()
+        }
+        InputDistribution::RandomPermutation => rand_vec(i),
+    }
+}
+
+/// Like [`table_count_operations`], but generates each row's input under
+/// `dist` instead of always a random permutation.
+pub fn table_count_operations_for<F>(mut i: usize, j: usize, dist: InputDistribution, f: F)
+where
+    F: Fn(&mut [Instrumented<u64>]),
+{
+    let mut sizes = Vec::new();
+    let mut rows = Vec::new();
+    while i <= j {
+        let vec = generate_vec(i, dist);
+        rows.push(count_operations(vec, &f).get());
+        sizes.push(i);
+
+        i <<= 1;
+    }
+    print_table(&sizes, &rows);
+}
+
+/// Tabulates several input distributions side by side, one table per
+/// distribution, so e.g. a sort's behavior on sorted vs. reversed vs.
+/// many-duplicate input can be compared directly.
+pub fn table_count_operations_distributions<F>(
+    i: usize,
+    j: usize,
+    dists: &[InputDistribution],
+    f: F,
+) where
+    F: Fn(&mut [Instrumented<u64>]),
+{
+    for &dist in dists {
+        println!("{dist:?}");
+        table_count_operations_for(i, j, dist, &f);
+    }
+}
+
+fn print_table(sizes: &[usize], rows: &[[usize; InstrumentedBase::COLUMNS]]) {
     let mut table = Table::new();
     table.set_format(*FORMAT_NO_BORDER_LINE_SEPARATOR);
     let hader = InstrumentedBase::counts_names()
         .iter()
-        .map(|x| Cell::new(x))
+        .enumerate()
+        .map(|(col, name)| {
+            let column: Vec<usize> = rows.iter().map(|r| r[col]).collect();
+            let class = classify_growth(sizes, &column);
+            Cell::new(&format!("{name}: {class}"))
+        })
         .collect();
     table.set_titles(Row::new(hader));
+    for row in rows {
+        let c = row.iter().map(|x| Cell::new(&x.to_string())).collect();
+        table.add_row(Row::new(c));
+    }
+    table.printstd();
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ColumnStats {
+    min: usize,
+    max: usize,
+    mean: f64,
+    sd: f64,
+}
+
+impl std::fmt::Display for ColumnStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(
+            f,
+            "{:.1} \u{b1} {:.1} [{}, {}]",
+            self.mean, self.sd, self.min, self.max
+        )
+    }
+}
+
+fn column_stats(samples: &[usize]) -> ColumnStats {
+    let min = *samples.iter().min().unwrap();
+    let max = *samples.iter().max().unwrap();
+    let n = samples.len() as f64;
+    let mean = samples.iter().sum::<usize>() as f64 / n;
+    let variance = samples
+        .iter()
+        .map(|&x| {
+            let d = x as f64 - mean;
+            d * d
+        })
+        .sum::<f64>()
+        / n;
+    ColumnStats {
+        min,
+        max,
+        mean,
+        sd: variance.sqrt(),
+    }
+}
+
+/// Like [`table_count_operations_for`], but runs `trials` freshly generated
+/// inputs per size and reports each column's min/max/mean/standard-deviation
+/// instead of a single noisy sample. `trials == 1` falls back to the
+/// original single-sample table, which stays deterministic enough for tests.
+pub fn table_count_operations_trials<F>(
+    mut i: usize,
+    j: usize,
+    dist: InputDistribution,
+    trials: usize,
+    f: F,
+) where
+    F: Fn(&mut [Instrumented<u64>]),
+{
+    let trials = trials.max(1);
+    if trials == 1 {
+        table_count_operations_for(i, j, dist, f);
+        return;
+    }
+
+    let mut sizes = Vec::new();
+    let mut stats_rows: Vec<Vec<ColumnStats>> = Vec::new();
     while i <= j {
-        let vec = rand_vec(i);
+        let mut columns: Vec<Vec<usize>> = vec![Vec::new(); InstrumentedBase::COLUMNS];
+        for _ in 0..trials {
+            let vec = generate_vec(i, dist);
+            let counts = count_operations(vec, &f).get();
+            for (col, samples) in columns.iter_mut().enumerate() {
+                samples.push(counts[col]);
+            }
+        }
+        stats_rows.push(columns.iter().map(|s| column_stats(s)).collect());
+        sizes.push(i);
+
+        i <<= 1;
+    }
 
-        let c = count_operations(vec, &f)
-            .get()
+    let mut table = Table::new();
+    table.set_format(*FORMAT_NO_BORDER_LINE_SEPARATOR);
+    let hader = InstrumentedBase::counts_names()
+        .iter()
+        .enumerate()
+        .map(|(col, name)| {
+            let column: Vec<usize> = stats_rows
+                .iter()
+                .map(|r| r[col].mean.round() as usize)
+                .collect();
+            let class = classify_growth(&sizes, &column);
+            Cell::new(&format!("{name}: {class}"))
+        })
+        .collect();
+    table.set_titles(Row::new(hader));
+    for row in &stats_rows {
+        let c = row
             .iter()
-            .map(|x| Cell::new(&x.to_string()))
+            .map(|stat| Cell::new(&stat.to_string()))
             .collect();
         table.add_row(Row::new(c));
-
-        i <<= 1;
     }
     table.printstd();
 }
 
+/// Empirical complexity classes that [`classify_growth`] can recognize from
+/// a table of operation counts sampled across doubling input sizes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComplexityClass {
+    Constant,
+    Linear,
+    Linearithmic,
+    Quadratic,
+    Exponential,
+}
+
+impl std::fmt::Display for ComplexityClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        let s = match self {
+            ComplexityClass::Constant => "\u{398}(1)",
+            ComplexityClass::Linear => "\u{398}(n)",
+            ComplexityClass::Linearithmic => "\u{398}(n log₂n)",
+            ComplexityClass::Quadratic => "\u{398}(n²)",
+            ComplexityClass::Exponential => "\u{398}(n·2ⁿ)",
+        };
+        f.write_str(s)
+    }
+}
+
+const COMPLEXITY_CLASSES: [ComplexityClass; 4] = [
+    ComplexityClass::Linear,
+    ComplexityClass::Linearithmic,
+    ComplexityClass::Quadratic,
+    ComplexityClass::Exponential,
+];
+
+fn predicted_ratio(class: ComplexityClass, n: f64) -> f64 {
+    match class {
+        ComplexityClass::Constant => 1.0,
+        ComplexityClass::Linear => 2.0,
+        ComplexityClass::Linearithmic => 2.0 * (1.0 + 1.0 / n.max(2.0).log2()),
+        ComplexityClass::Quadratic => 4.0,
+        ComplexityClass::Exponential => 2.0 * 2f64.powf(n),
+    }
+}
+
+/// Classifies the empirical growth of one operation-count column, sampled
+/// at doubling input sizes, by comparing the observed `count(2n)/count(n)`
+/// ratios against the ratios predicted by each candidate complexity class
+/// and picking the class with the least squared log-ratio error. A column
+/// that never moves (e.g. an operation the algorithm under test never
+/// performs) has no growth ratio to compare against, so it is reported as
+/// [`ComplexityClass::Constant`] rather than defaulting to `Linear`.
+pub fn classify_growth(sizes: &[usize], counts: &[usize]) -> ComplexityClass {
+    if counts
+        .first()
+        .is_some_and(|&first| counts.iter().all(|&c| c == first))
+    {
+        return ComplexityClass::Constant;
+    }
+    COMPLEXITY_CLASSES
+        .into_iter()
+        .min_by(|&a, &b| {
+            growth_error(a, sizes, counts)
+                .partial_cmp(&growth_error(b, sizes, counts))
+                .unwrap()
+        })
+        .unwrap_or(ComplexityClass::Linear)
+}
+
+fn growth_error(class: ComplexityClass, sizes: &[usize], counts: &[usize]) -> f64 {
+    sizes
+        .windows(2)
+        .zip(counts.windows(2))
+        .filter(|(_, c)| c[0] > 0 && c[1] > 0)
+        .map(|(s, c)| {
+            let observed = c[1] as f64 / c[0] as f64;
+            let predicted = predicted_ratio(class, s[0] as f64);
+            (observed.ln() - predicted.ln()).powi(2)
+        })
+        .sum()
+}
+
 fn rand_vec(i: usize) -> Vec<u64> {
     use rand::seq::SliceRandom;
     use rand::thread_rng;
@@ -167,6 +494,220 @@ where
     base3
 }
 
+type AtomicCounts = [AtomicUsize; InstrumentedBase::COLUMNS];
+
+/// Thread-safe counterpart of [`Instrumented`]: the same counted operations,
+/// but backed by an `Arc` of atomics instead of an `Rc<RefCell<_>>`, so
+/// `InstrumentedAtomic<T>` is `Send`/`Sync` whenever `T` is, and can
+/// instrument parallel algorithms.
+pub struct InstrumentedAtomic<T> {
+    value: T,
+    base: Arc<AtomicCounts>,
+}
+
+fn new_atomic_base() -> Arc<AtomicCounts> {
+    Arc::new([(); InstrumentedBase::COLUMNS].map(|_| AtomicUsize::new(0)))
+}
+
+impl<T> std::fmt::Debug for InstrumentedAtomic<T>
+where
+    T: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        self.value.fmt(f)
+    }
+}
+
+/// Conversion
+impl<T> InstrumentedAtomic<T> {
+    pub fn new(value: T, base: Arc<AtomicCounts>) -> Self {
+        base[InstrumentedBase::NEW].fetch_add(1, Ordering::Relaxed);
+        Self { value, base }
+    }
+
+    /// Reads the wrapped value without counting an operation.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+}
+
+/// Default constructible
+impl<T> InstrumentedAtomic<T>
+where
+    T: Default,
+{
+    pub fn default(base: Arc<AtomicCounts>) -> Self {
+        base[InstrumentedBase::DEFAULT].fetch_add(1, Ordering::Relaxed);
+        Self {
+            value: T::default(),
+            base,
+        }
+    }
+}
+
+/// Semi regular
+impl<T> Clone for InstrumentedAtomic<T>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        self.base[InstrumentedBase::CLONE].fetch_add(1, Ordering::Relaxed);
+        Self {
+            value: self.value.clone(),
+            base: self.base.clone(),
+        }
+    }
+}
+
+/// Semi regular
+impl<T> InstrumentedAtomic<T>
+where
+    T: Clone,
+{
+    pub fn assign(&mut self, x: &Self) {
+        self.base[InstrumentedBase::ASSIGN].fetch_add(1, Ordering::Relaxed);
+        self.value.clone_from(&x.value);
+    }
+}
+
+/// Semi regular
+impl<T> Drop for InstrumentedAtomic<T> {
+    fn drop(&mut self) {
+        self.base[InstrumentedBase::DROP].fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Regular
+impl<T> PartialEq for InstrumentedAtomic<T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, x: &Self) -> bool {
+        self.base[InstrumentedBase::EQ].fetch_add(1, Ordering::Relaxed);
+        self.value.eq(&x.value)
+    }
+}
+
+impl<T> Eq for InstrumentedAtomic<T> where T: Eq {}
+
+/// Regular
+impl<T> std::hash::Hash for InstrumentedAtomic<T>
+where
+    T: std::hash::Hash,
+{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.base[InstrumentedBase::HASH].fetch_add(1, Ordering::Relaxed);
+        self.value.hash(state);
+    }
+}
+
+/// Totally-ordered
+impl<T> PartialOrd for InstrumentedAtomic<T>
+where
+    T: PartialOrd,
+{
+    fn partial_cmp(&self, x: &Self) -> Option<std::cmp::Ordering> {
+        self.base[InstrumentedBase::PARTIAL_CMP].fetch_add(1, Ordering::Relaxed);
+        self.value.partial_cmp(&x.value)
+    }
+}
+
+/// Totally-ordered
+impl<T> Ord for InstrumentedAtomic<T>
+where
+    T: Ord,
+{
+    fn cmp(&self, x: &Self) -> std::cmp::Ordering {
+        self.base[InstrumentedBase::CMP].fetch_add(1, Ordering::Relaxed);
+        self.value.cmp(&x.value)
+    }
+}
+
+/// Semi regular
+pub fn swap_atomic<T>(a: &mut InstrumentedAtomic<T>, b: &mut InstrumentedAtomic<T>) {
+    a.base[InstrumentedBase::SWAP].fetch_add(1, Ordering::Relaxed);
+    std::mem::swap(&mut a.value, &mut b.value);
+}
+
+fn atomic_snapshot(base: &AtomicCounts) -> InstrumentedBase {
+    let mut counts = [0usize; InstrumentedBase::COLUMNS];
+    for (c, a) in counts.iter_mut().zip(base.iter()) {
+        *c = a.load(Ordering::Relaxed);
+    }
+    let mut result: InstrumentedBase = Default::default();
+    result.set(counts);
+    result
+}
+
+/// Like [`count_operations`], but instruments with [`InstrumentedAtomic`] so
+/// `f` may fan its work across threads (e.g. a parallel sort) instead of
+/// running sequentially.
+pub fn count_operations_atomic<T, F>(vec: Vec<T>, f: F) -> InstrumentedBase
+where
+    T: Send + Sync,
+    F: Fn(&mut [InstrumentedAtomic<T>]),
+{
+    let base = new_atomic_base();
+    let mut vec: Vec<InstrumentedAtomic<T>> = vec
+        .into_iter()
+        .map(|x| InstrumentedAtomic::new(x, base.clone()))
+        .collect();
+    f(&mut vec);
+    atomic_snapshot(&base)
+}
+
+/// Wraps a user key function so every invocation bumps `key_eval`, letting a
+/// single run compare e.g. `sort_by_key` against `sort_by_cached_key`. Holds
+/// the key closure directly (no `Box<dyn Fn>`), so it stays as cheap as the
+/// rest of the crate's generic-closure API and may borrow local data.
+pub struct CountedKey<A, B, K>
+where
+    K: Fn(&A) -> B,
+{
+    base: Rc<RefCell<InstrumentedBase>>,
+    key: K,
+    marker: std::marker::PhantomData<fn(&A) -> B>,
+}
+
+impl<A, B, K> CountedKey<A, B, K>
+where
+    K: Fn(&A) -> B,
+{
+    pub fn new(base: Rc<RefCell<InstrumentedBase>>, key: K) -> Self {
+        Self {
+            base,
+            key,
+            marker: std::marker::PhantomData,
+        }
+    }
+
+    pub fn call(&self, a: &A) -> B {
+        self.base.borrow_mut().counts[InstrumentedBase::KEY_EVAL] += 1;
+        (self.key)(a)
+    }
+}
+
+/// Variant of [`count_operations`] that also threads a [`CountedKey`] built
+/// from `key` through to `f`, so comparisons, clones, and key evaluations
+/// are all reported from the same run. Use [`Instrumented::value`] inside
+/// `key` to read the wrapped value.
+pub fn count_operations_with_key<T, B, K, F>(vec: Vec<T>, key: K, f: F) -> InstrumentedBase
+where
+    K: Fn(&Instrumented<T>) -> B,
+    F: Fn(&mut [Instrumented<T>], &CountedKey<Instrumented<T>, B, K>),
+{
+    let base = Rc::new(RefCell::new(Default::default()));
+    let mut vec: Vec<Instrumented<T>> = vec
+        .into_iter()
+        .map(|x| Instrumented::new(x, base.clone()))
+        .collect();
+    let counted_key = CountedKey::new(base.clone(), key);
+    f(&mut vec, &counted_key);
+    let base2: RefCell<InstrumentedBase> = (*base).clone();
+    let base3: InstrumentedBase = *base2.borrow();
+    base3
+}
+
 #[cfg(test)]
 mod tests {
     use super::count_operations;
@@ -178,7 +719,7 @@ mod tests {
         (0..4).for_each(|k| vec.push(k));
         let one = count_operations(vec, |x| x.sort());
         let mut def: InstrumentedBase = Default::default();
-        def.set([4, 0, 0, 0, 3, 0]);
+        def.set([4, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0]);
         assert_eq!(def, one);
     }
     #[test]
@@ -187,12 +728,233 @@ mod tests {
         (0..4).for_each(|k| vec.push(3 - k));
         let one = count_operations(vec, |x| x.sort());
         let mut def: InstrumentedBase = Default::default();
-        def.set([4, 0, 0, 0, 6, 0]);
+        def.set([4, 0, 0, 0, 6, 0, 0, 0, 0, 0, 0]);
         assert_eq!(def, one);
     }
     #[test]
     fn print() {
         let n = count_operations::<u64, _>(vec![], |_x| ());
-        assert_eq!("[(\"new\", 0), (\"clone\", 0), (\"drop\", 0), (\"eq\", 0), (\"partial_cmp\", 0), (\"cmp\", 0)]", format!("{:?}", n));
+        assert_eq!("[(\"new\", 0), (\"clone\", 0), (\"drop\", 0), (\"eq\", 0), (\"partial_cmp\", 0), (\"cmp\", 0), (\"assign\", 0), (\"swap\", 0), (\"default\", 0), (\"key_eval\", 0), (\"hash\", 0)]", format!("{:?}", n));
+    }
+    #[test]
+    fn it_assign() {
+        let vec = vec![1, 2];
+        let one = count_operations(vec, |x| {
+            let (a, b) = x.split_at_mut(1);
+            a[0].assign(&b[0]);
+        });
+        let mut def: InstrumentedBase = Default::default();
+        def.set([2, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0]);
+        assert_eq!(def, one);
+    }
+    #[test]
+    fn it_default() {
+        use super::Instrumented;
+        use std::cell::RefCell;
+        use std::rc::Rc;
+        let base = Rc::new(RefCell::new(InstrumentedBase::default()));
+        let one: Instrumented<u64> = Instrumented::default(base.clone());
+        assert_eq!(0, *one.value());
+        assert_eq!(1, base.borrow().get()[InstrumentedBase::DEFAULT]);
+    }
+    #[test]
+    fn it_swap() {
+        let vec = vec![1, 2];
+        let one = count_operations(vec, |x| {
+            let (a, b) = x.split_at_mut(1);
+            super::swap(&mut a[0], &mut b[0]);
+        });
+        let mut def: InstrumentedBase = Default::default();
+        def.set([2, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0]);
+        assert_eq!(def, one);
+    }
+    #[test]
+    fn it_key_eval() {
+        use super::count_operations_with_key;
+        let vec = vec![3u64, 1, 2];
+        let one = count_operations_with_key(
+            vec,
+            |v| *v.value(),
+            |x, key| {
+                x.sort_by_key(|v| key.call(v));
+            },
+        );
+        assert!(one.get()[InstrumentedBase::KEY_EVAL] >= 3);
+    }
+    #[test]
+    fn it_key_eval_cached_vs_uncached() {
+        use super::count_operations_with_key;
+        let n = 64usize;
+        let vec: Vec<u64> = (0..n as u64).map(|i| (i * 41 + 7) % n as u64).collect();
+        let key = |v: &super::Instrumented<u64>| *v.value();
+
+        let uncached = count_operations_with_key(vec.clone(), key, |x, key| {
+            x.sort_by_key(|v| key.call(v));
+        });
+        let cached = count_operations_with_key(vec, key, |x, key| {
+            x.sort_by_cached_key(|v| key.call(v));
+        });
+
+        assert_eq!(n, cached.get()[InstrumentedBase::KEY_EVAL]);
+        assert!(uncached.get()[InstrumentedBase::KEY_EVAL] > n * 2);
+    }
+    #[test]
+    fn it_classify_linear() {
+        use super::{classify_growth, ComplexityClass};
+        let sizes = [8, 16, 32, 64];
+        let counts = [8, 16, 32, 64];
+        assert_eq!(ComplexityClass::Linear, classify_growth(&sizes, &counts));
+    }
+    #[test]
+    fn it_classify_linearithmic() {
+        use super::{classify_growth, ComplexityClass};
+        let sizes = [8, 16, 32, 64, 128];
+        let counts = [24, 64, 160, 384, 896];
+        assert_eq!(
+            ComplexityClass::Linearithmic,
+            classify_growth(&sizes, &counts)
+        );
+    }
+    #[test]
+    fn it_classify_quadratic() {
+        use super::{classify_growth, ComplexityClass};
+        let sizes = [8, 16, 32, 64];
+        let counts = [64, 256, 1024, 4096];
+        assert_eq!(ComplexityClass::Quadratic, classify_growth(&sizes, &counts));
+    }
+    #[test]
+    fn it_classify_constant() {
+        use super::{classify_growth, ComplexityClass};
+        let sizes = [8, 16, 32, 64];
+        let counts = [0, 0, 0, 0];
+        assert_eq!(ComplexityClass::Constant, classify_growth(&sizes, &counts));
+        let counts = [5, 5, 5, 5];
+        assert_eq!(ComplexityClass::Constant, classify_growth(&sizes, &counts));
+    }
+    #[test]
+    fn it_distribution_sorted() {
+        use super::{generate_vec, InputDistribution};
+        assert_eq!(vec![0, 1, 2, 3], generate_vec(4, InputDistribution::Sorted));
+    }
+    #[test]
+    fn it_distribution_reversed() {
+        use super::{generate_vec, InputDistribution};
+        assert_eq!(
+            vec![3, 2, 1, 0],
+            generate_vec(4, InputDistribution::Reversed)
+        );
+    }
+    #[test]
+    fn it_distribution_sawtooth() {
+        use super::{generate_vec, InputDistribution};
+        assert_eq!(
+            vec![0, 1, 2, 0, 1, 2],
+            generate_vec(6, InputDistribution::Sawtooth { period: 3 })
+        );
+    }
+    #[test]
+    fn it_distribution_few_unique_values() {
+        use super::{generate_vec, InputDistribution};
+        let vec = generate_vec(6, InputDistribution::FewUniqueValues { classes: 2 });
+        assert_eq!(6, vec.len());
+        assert!(vec.iter().all(|&x| x < 2));
+    }
+    #[test]
+    fn it_column_stats() {
+        use super::column_stats;
+        let stats = column_stats(&[2, 4, 6]);
+        assert_eq!(2, stats.min);
+        assert_eq!(6, stats.max);
+        assert!((stats.mean - 4.0).abs() < 1e-9);
+        assert!((stats.sd - (8.0f64 / 3.0).sqrt()).abs() < 1e-9);
+    }
+    #[test]
+    fn it_atomic_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<super::InstrumentedAtomic<u64>>();
+    }
+    #[test]
+    fn it_atomic_sort() {
+        use super::count_operations_atomic;
+        let mut vec = Vec::new();
+        (0..4).for_each(|k| vec.push(k));
+        let one = count_operations_atomic(vec, |x| x.sort());
+        let mut def: InstrumentedBase = Default::default();
+        def.set([4, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(def, one);
+    }
+    #[test]
+    fn it_atomic_across_threads() {
+        use super::count_operations_atomic;
+        let vec: Vec<u64> = (0..8).collect();
+        let one = count_operations_atomic(vec, |x| {
+            let (a, b) = x.split_at_mut(4);
+            std::thread::scope(|scope| {
+                scope.spawn(|| a.sort());
+                scope.spawn(|| b.sort());
+            });
+        });
+        assert_eq!(8, one.get()[InstrumentedBase::NEW]);
+    }
+    #[test]
+    #[allow(clippy::mutable_key_type)]
+    fn it_hash() {
+        use std::collections::HashSet;
+        let mut vec = Vec::new();
+        (0..4).for_each(|k| vec.push(k));
+        vec.push(0);
+        let one = count_operations(vec, |x| {
+            let set: HashSet<_> = x.iter().cloned().collect();
+            assert_eq!(4, set.len());
+        });
+        assert!(one.get()[InstrumentedBase::HASH] >= 5);
+    }
+    #[test]
+    fn it_atomic_assign() {
+        use super::count_operations_atomic;
+        let vec = vec![1, 2];
+        let one = count_operations_atomic(vec, |x| {
+            let (a, b) = x.split_at_mut(1);
+            a[0].assign(&b[0]);
+        });
+        let mut def: InstrumentedBase = Default::default();
+        def.set([2, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0]);
+        assert_eq!(def, one);
+    }
+    #[test]
+    fn it_atomic_swap() {
+        use super::count_operations_atomic;
+        let vec = vec![1, 2];
+        let one = count_operations_atomic(vec, |x| {
+            let (a, b) = x.split_at_mut(1);
+            super::swap_atomic(&mut a[0], &mut b[0]);
+        });
+        let mut def: InstrumentedBase = Default::default();
+        def.set([2, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0]);
+        assert_eq!(def, one);
+    }
+    #[test]
+    fn it_atomic_default() {
+        let base = super::new_atomic_base();
+        let one: super::InstrumentedAtomic<u64> = super::InstrumentedAtomic::default(base.clone());
+        assert_eq!(0, *one.value());
+        assert_eq!(
+            1,
+            base[InstrumentedBase::DEFAULT].load(std::sync::atomic::Ordering::Relaxed)
+        );
+    }
+    #[test]
+    #[allow(clippy::mutable_key_type)]
+    fn it_atomic_hash() {
+        use super::count_operations_atomic;
+        use std::collections::HashSet;
+        let mut vec = Vec::new();
+        (0..4).for_each(|k| vec.push(k));
+        vec.push(0);
+        let one = count_operations_atomic(vec, |x| {
+            let set: HashSet<_> = x.iter().cloned().collect();
+            assert_eq!(4, set.len());
+        });
+        assert!(one.get()[InstrumentedBase::HASH] >= 5);
     }
 }